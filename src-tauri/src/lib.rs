@@ -1,8 +1,115 @@
-use tauri_plugin_sql::{Migration, MigrationKind};
+use tauri_plugin_sql::{DbInstances, DbPool, Migration, MigrationKind};
 use flate2::read::GzDecoder;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+
+fn json_string_list(text: &str) -> Vec<String> {
+    serde_json::from_str(text).unwrap_or_default()
+}
+
+/// Builds a safe FTS5 `MATCH` query from free-form user input: splits on whitespace, quotes
+/// each token as a phrase (doubling any embedded `"`), and appends `*` for prefix matching.
+/// Quoting each token means hyphens, colons and apostrophes are treated as literal text
+/// instead of being parsed as FTS5's NOT/column-filter/syntax operators. Adjacent quoted
+/// phrases are ANDed together implicitly by FTS5. Returns an empty string if `query` has no
+/// usable tokens.
+fn build_fts5_match_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|token| format!("\"{}\"*", token.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod fts5_match_query_tests {
+    use super::*;
+
+    #[test]
+    fn single_word_is_quoted_and_prefixed() {
+        assert_eq!(build_fts5_match_query("hello"), "\"hello\"*");
+    }
+
+    #[test]
+    fn multi_word_query_quotes_each_token() {
+        assert_eq!(
+            build_fts5_match_query("well-being apostrophe's a:b"),
+            "\"well-being\"* \"apostrophe's\"* \"a:b\"*"
+        );
+    }
+
+    #[test]
+    fn embedded_double_quote_is_doubled() {
+        assert_eq!(build_fts5_match_query("foo\"bar"), "\"foo\"\"bar\"*");
+    }
+
+    #[test]
+    fn whitespace_only_query_is_empty() {
+        assert_eq!(build_fts5_match_query("   "), "");
+    }
+
+    #[test]
+    fn empty_query_is_empty() {
+        assert_eq!(build_fts5_match_query(""), "");
+    }
+}
+
+/// Creates (if missing) the `dictionary_fts` FTS5 index over a decompressed
+/// `dictionary_*.db` and keeps it in sync with the `entries` table via triggers.
+/// Safe to call every time a dictionary is opened: it checks `sqlite_master` first
+/// and does nothing beyond that lookup once the index already exists.
+fn ensure_dictionary_fts_index(db_path: &std::path::Path) -> Result<(), String> {
+    let conn = rusqlite::Connection::open(db_path)
+        .map_err(|e| format!("Failed to open dictionary db: {}", e))?;
+
+    let already_indexed: bool = conn
+        .query_row(
+            "SELECT count(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'dictionary_fts'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to check dictionary FTS index: {}", e))?;
+
+    if already_indexed {
+        return Ok(());
+    }
+
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS dictionary_fts USING fts5(
+            word, meanings, synonyms, notes, content='entries', content_rowid='id'
+         );
+         CREATE TRIGGER IF NOT EXISTS entries_ai AFTER INSERT ON entries BEGIN
+           INSERT INTO dictionary_fts(rowid, word, meanings, synonyms, notes)
+           VALUES (new.id, new.word, new.meanings, new.synonyms, new.notes);
+         END;
+         CREATE TRIGGER IF NOT EXISTS entries_ad AFTER DELETE ON entries BEGIN
+           INSERT INTO dictionary_fts(dictionary_fts, rowid, word, meanings, synonyms, notes)
+           VALUES('delete', old.id, old.word, old.meanings, old.synonyms, old.notes);
+         END;
+         CREATE TRIGGER IF NOT EXISTS entries_au AFTER UPDATE ON entries BEGIN
+           INSERT INTO dictionary_fts(dictionary_fts, rowid, word, meanings, synonyms, notes)
+           VALUES('delete', old.id, old.word, old.meanings, old.synonyms, old.notes);
+           INSERT INTO dictionary_fts(rowid, word, meanings, synonyms, notes)
+           VALUES (new.id, new.word, new.meanings, new.synonyms, new.notes);
+         END;",
+    )
+    .map_err(|e| format!("Failed to create dictionary FTS index: {}", e))?;
+
+    conn.execute(
+        "INSERT INTO dictionary_fts(rowid, word, meanings, synonyms, notes)
+         SELECT id, word, meanings, synonyms, notes FROM entries",
+        [],
+    )
+    .map_err(|e| format!("Failed to populate dictionary FTS index: {}", e))?;
+
+    Ok(())
+}
+
+const DEFAULT_MIRROR_URL: &str = "https://dictionaries.example.com";
 
 #[derive(serde::Serialize, serde::Deserialize)]
 struct DictionaryEntry {
@@ -24,11 +131,479 @@ struct DictionaryInfo {
     logs: Vec<String>,
 }
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct DictionaryCatalogEntry {
+    lang: String,
+    version: String,
+    url: String,
+    compressed_size: u64,
+    sha256: String,
+}
+
+#[derive(serde::Serialize)]
+struct InstalledDictionary {
+    lang: String,
+    version: Option<String>,
+    path: String,
+    size_bytes: u64,
+}
+
+#[derive(serde::Serialize, Clone)]
+struct DownloadProgress {
+    lang: String,
+    downloaded: u64,
+    total: u64,
+}
+
+#[derive(Default)]
+struct DictionaryCatalog(tokio::sync::Mutex<Vec<DictionaryCatalogEntry>>);
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct AppConfig {
+    #[serde(rename = "mirrorUrls")]
+    mirror_urls: Vec<String>,
+    #[serde(rename = "deleteGzAfterDecompress")]
+    delete_gz_after_decompress: bool,
+    #[serde(rename = "defaultLang")]
+    default_lang: String,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            mirror_urls: vec![DEFAULT_MIRROR_URL.to_string()],
+            delete_gz_after_decompress: false,
+            default_lang: "en".to_string(),
+        }
+    }
+}
+
+struct AppConfigState(tokio::sync::Mutex<AppConfig>);
+
+fn config_file_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?
+        .join("config.json"))
+}
+
+fn load_app_config(app: &tauri::AppHandle) -> AppConfig {
+    config_file_path(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_app_config(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), String> {
+    let path = config_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write config: {}", e))
+}
+
+/// Builds the list of candidate URLs to try for a dictionary's `.gz`, in order:
+/// the catalog-supplied URL (if any) first, then each configured mirror base URL.
+fn dictionary_gz_candidate_urls(mirrors: &[String], lang: &str, catalog_url: Option<&str>) -> Vec<String> {
+    let mut urls: Vec<String> = catalog_url.map(|u| u.to_string()).into_iter().collect();
+    urls.extend(
+        mirrors
+            .iter()
+            .map(|mirror| format!("{}/dictionary_{}.db.gz", mirror.trim_end_matches('/'), lang)),
+    );
+    urls
+}
+
+fn catalog_candidate_urls(mirrors: &[String]) -> Vec<String> {
+    mirrors
+        .iter()
+        .map(|mirror| format!("{}/catalog.json", mirror.trim_end_matches('/')))
+        .collect()
+}
+
+// Get the persisted app configuration (download mirrors, gz retention, default language).
+#[tauri::command]
+async fn get_config(config: tauri::State<'_, AppConfigState>) -> Result<AppConfig, String> {
+    Ok(config.0.lock().await.clone())
+}
+
+// Persist a new app configuration to disk and make it the active one.
+#[tauri::command]
+async fn set_config(
+    app: tauri::AppHandle,
+    config: tauri::State<'_, AppConfigState>,
+    new_config: AppConfig,
+) -> Result<(), String> {
+    save_app_config(&app, &new_config)?;
+    *config.0.lock().await = new_config;
+    Ok(())
+}
+
+/// Validates a language code before it is used to build any filesystem path or URL,
+/// rejecting anything outside `[a-z]{2,8}` (blocks path traversal via `.`/`/`/`..`).
+fn validate_lang(lang: &str) -> Result<(), String> {
+    let is_valid = (2..=8).contains(&lang.len()) && lang.bytes().all(|b| b.is_ascii_lowercase());
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid language code: {:?}", lang))
+    }
+}
+
+fn version_sidecar_path(app: &tauri::AppHandle, lang: &str) -> Result<std::path::PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(format!("dictionary_{}.version", lang)))
+}
+
+fn sha256_sidecar_path(app: &tauri::AppHandle, lang: &str) -> Result<std::path::PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(format!("dictionary_{}.db.sha256", lang)))
+}
+
+/// Path to the sidecar file carrying the expected SHA-256 of a dictionary's decompressed
+/// content, written next to the `.gz` download before it is available from the catalog.
+fn expected_sha256_sidecar_path(app: &tauri::AppHandle, lang: &str) -> Result<std::path::PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(format!("dictionary_{}.db.gz.sha256", lang)))
+}
+
+/// Wraps a `Write` and feeds every byte written through a SHA-256 hasher, so callers
+/// can hash a stream while it is copied without buffering it twice.
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn sha256_hex_of_file(path: &std::path::Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to hash {:?}: {}", path, e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Streams `response`'s body into `gz_path`, emitting progress events as it goes, then
+/// decompresses it into `db_path`. A failed decompression removes the partial `db_path`
+/// (see `decompress_gz_to_db`); `gz_path` is never cleaned up here — callers do that since
+/// they know whether the `.gz` should be kept.
+async fn download_gz_and_decompress(
+    app: tauri::AppHandle,
+    lang: String,
+    gz_path: std::path::PathBuf,
+    db_path: std::path::PathBuf,
+    total: u64,
+    expected_sha256: Option<String>,
+    response: reqwest::Response,
+) -> Result<(u64, u64, String), String> {
+    let mut file = File::create(&gz_path).map_err(|e| format!("Failed to create GZ file: {}", e))?;
+    let mut downloaded: u64 = 0;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read download chunk: {}", e))?;
+        std::io::Write::write_all(&mut file, &chunk)
+            .map_err(|e| format!("Failed to write GZ file: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        app.emit(
+            "dictionary://download-progress",
+            DownloadProgress {
+                lang: lang.clone(),
+                downloaded,
+                total,
+            },
+        )
+        .map_err(|e| format!("Failed to emit progress event: {}", e))?;
+    }
+
+    let (bytes_written, actual_sha256) = decompress_gz_to_db(&gz_path, &db_path, expected_sha256.as_deref())?;
+
+    Ok((downloaded, bytes_written, actual_sha256))
+}
+
+/// Prefixes an error with the progress `logs` collected so far, so a caller surfacing only
+/// the returned `Err(String)` (e.g. via `?`) doesn't lose the trail leading up to it.
+fn fold_logs_into_error(logs: &[String], error: &str) -> String {
+    if logs.is_empty() {
+        error.to_string()
+    } else {
+        format!("{}\n[RUST] Error: {}", logs.join("\n"), error)
+    }
+}
+
+/// Decompresses `gz_path` into `db_path`, hashing the decompressed bytes as they stream
+/// through. If `expected_sha256` is given and doesn't match, the partial `.db` is deleted
+/// and an `Err` is returned instead of leaving a corrupt file behind.
+fn decompress_gz_to_db(
+    gz_path: &std::path::Path,
+    db_path: &std::path::Path,
+    expected_sha256: Option<&str>,
+) -> Result<(u64, String), String> {
+    match decompress_gz_to_db_inner(gz_path, db_path, expected_sha256) {
+        Ok(result) => Ok(result),
+        Err(e) => {
+            std::fs::remove_file(db_path).ok();
+            Err(e)
+        }
+    }
+}
+
+fn decompress_gz_to_db_inner(
+    gz_path: &std::path::Path,
+    db_path: &std::path::Path,
+    expected_sha256: Option<&str>,
+) -> Result<(u64, String), String> {
+    let bytes = std::fs::read(gz_path).map_err(|e| format!("Failed to read GZ file: {}", e))?;
+
+    let mut decoder = GzDecoder::new(&bytes[..]);
+    let output_file = File::create(db_path).map_err(|e| format!("Failed to create DB file: {}", e))?;
+    let mut hashing_writer = HashingWriter {
+        inner: output_file,
+        hasher: Sha256::new(),
+    };
+
+    let bytes_written = std::io::copy(&mut decoder, &mut hashing_writer)
+        .map_err(|e| format!("Failed to decompress DB: {}", e))?;
+    let actual_sha256 = format!("{:x}", hashing_writer.hasher.finalize());
+
+    if let Some(expected) = expected_sha256 {
+        if !expected.eq_ignore_ascii_case(&actual_sha256) {
+            return Err(format!(
+                "Checksum mismatch for {:?}: expected {}, got {}",
+                db_path, expected, actual_sha256
+            ));
+        }
+    }
+
+    Ok((bytes_written, actual_sha256))
+}
+
+// Download a JSON manifest describing every dictionary available for download,
+// trying each configured mirror in order until one responds.
+#[tauri::command]
+async fn fetch_dictionary_catalog(
+    catalog: tauri::State<'_, DictionaryCatalog>,
+    config: tauri::State<'_, AppConfigState>,
+) -> Result<Vec<DictionaryCatalogEntry>, String> {
+    let mirrors = config.0.lock().await.mirror_urls.clone();
+
+    let mut last_error = "No catalog mirrors configured".to_string();
+    for url in catalog_candidate_urls(&mirrors) {
+        match reqwest::get(&url).await {
+            Ok(response) => match response.json::<Vec<DictionaryCatalogEntry>>().await {
+                Ok(entries) => {
+                    *catalog.0.lock().await = entries.clone();
+                    return Ok(entries);
+                }
+                Err(e) => last_error = format!("Failed to parse catalog from {}: {}", url, e),
+            },
+            Err(e) => last_error = format!("Failed to fetch catalog from {}: {}", url, e),
+        }
+    }
+
+    Err(last_error)
+}
+
+// Scan app_data_dir for installed dictionary_*.db files and report their version.
+#[tauri::command]
+async fn list_installed_dictionaries(
+    app: tauri::AppHandle,
+) -> Result<Vec<InstalledDictionary>, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+
+    let mut installed = Vec::new();
+
+    let read_dir = match std::fs::read_dir(&app_data_dir) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return Ok(installed),
+    };
+
+    for entry in read_dir {
+        let entry = entry.map_err(|e| format!("Failed to read app data dir: {}", e))?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+
+        let Some(lang) = file_name
+            .strip_prefix("dictionary_")
+            .and_then(|rest| rest.strip_suffix(".db"))
+        else {
+            continue;
+        };
+
+        let metadata = entry
+            .metadata()
+            .map_err(|e| format!("Failed to read metadata for {}: {}", file_name, e))?;
+
+        let version = std::fs::read_to_string(version_sidecar_path(&app, lang)?).ok();
+
+        installed.push(InstalledDictionary {
+            lang: lang.to_string(),
+            version,
+            path: entry.path().to_string_lossy().to_string(),
+            size_bytes: metadata.len(),
+        });
+    }
+
+    Ok(installed)
+}
+
+// Download a dictionary's .gz from the catalog manifest (or a configured mirror) into
+// app_data_dir, emitting progress events, then decompress it the same way
+// ensure_dictionary_db does.
+#[tauri::command]
+async fn download_dictionary(
+    app: tauri::AppHandle,
+    catalog: tauri::State<'_, DictionaryCatalog>,
+    config: tauri::State<'_, AppConfigState>,
+    lang: String,
+) -> Result<DictionaryInfo, String> {
+    validate_lang(&lang)?;
+    let mut logs = Vec::new();
+
+    let entry = {
+        let cached = catalog.0.lock().await;
+        cached.iter().find(|e| e.lang == lang).cloned()
+    };
+    let entry = match entry {
+        Some(entry) => Some(entry),
+        None => fetch_dictionary_catalog(catalog, config.clone())
+            .await
+            .ok()
+            .and_then(|entries| entries.into_iter().find(|e| e.lang == lang)),
+    };
+
+    let app_config = config.0.lock().await.clone();
+    let candidate_urls =
+        dictionary_gz_candidate_urls(&app_config.mirror_urls, &lang, entry.as_ref().map(|e| e.url.as_str()));
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let gz_path = app_data_dir.join(format!("dictionary_{}.db.gz", lang));
+    let db_path = app_data_dir.join(format!("dictionary_{}.db", lang));
+
+    let mut response = None;
+    let mut last_error = format!("No download mirrors configured for '{}'", lang);
+    for url in &candidate_urls {
+        logs.push(format!("[RUST] Trying dictionary ({}) at: {}", lang, url));
+        match reqwest::get(url).await.and_then(|r| r.error_for_status()) {
+            Ok(r) => {
+                response = Some(r);
+                break;
+            }
+            Err(e) => last_error = format!("Failed to download from {}: {}", url, e),
+        }
+    }
+    let response = response.ok_or_else(|| fold_logs_into_error(&logs, &last_error))?;
+
+    let total = response
+        .content_length()
+        .unwrap_or_else(|| entry.as_ref().map(|e| e.compressed_size).unwrap_or(0));
+
+    let expected_sha256 = entry.as_ref().map(|e| e.sha256.clone());
+    let (downloaded, bytes_written, _) = download_gz_and_decompress(
+        app.clone(),
+        lang.clone(),
+        gz_path.clone(),
+        db_path.clone(),
+        total,
+        expected_sha256,
+        response,
+    )
+    .await
+    .map_err(|e| {
+        std::fs::remove_file(&gz_path).ok();
+        fold_logs_into_error(&logs, &e)
+    })?;
+    logs.push(format!("[RUST] Downloaded {} bytes, decompressing...", downloaded));
+    logs.push(format!("[RUST] Successfully decompressed {} bytes", bytes_written));
+
+    ensure_dictionary_fts_index(&db_path).map_err(|e| fold_logs_into_error(&logs, &e))?;
+    logs.push("[RUST] FTS index ready.".to_string());
+
+    let version = entry.map(|e| e.version).unwrap_or_else(|| "unknown".to_string());
+    std::fs::write(version_sidecar_path(&app, &lang).map_err(|e| fold_logs_into_error(&logs, &e))?, &version)
+        .map_err(|e| fold_logs_into_error(&logs, &format!("Failed to write version sidecar: {}", e)))?;
+    // Hash the file as installed (after the FTS index was written into it), so that
+    // verify_dictionary_db compares against the same bytes it will re-hash.
+    let installed_sha256 = sha256_hex_of_file(&db_path).map_err(|e| fold_logs_into_error(&logs, &e))?;
+    std::fs::write(sha256_sidecar_path(&app, &lang).map_err(|e| fold_logs_into_error(&logs, &e))?, &installed_sha256)
+        .map_err(|e| fold_logs_into_error(&logs, &format!("Failed to write checksum sidecar: {}", e)))?;
+
+    if app_config.delete_gz_after_decompress {
+        std::fs::remove_file(&gz_path).ok();
+        logs.push("[RUST] Removed .gz after decompression.".to_string());
+    }
+
+    Ok(DictionaryInfo {
+        version,
+        path: db_path.to_string_lossy().to_string(),
+        exists: true,
+        logs,
+    })
+}
+
+// Re-hash an already-installed dictionary DB and compare it against the checksum
+// recorded the last time it was successfully downloaded and decompressed.
+#[tauri::command]
+async fn verify_dictionary_db(app: tauri::AppHandle, lang: String) -> Result<bool, String> {
+    validate_lang(&lang)?;
+    let db_path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(format!("dictionary_{}.db", lang));
+
+    if !db_path.exists() {
+        return Err(format!("Dictionary '{}' is not installed", lang));
+    }
+
+    let expected = std::fs::read_to_string(sha256_sidecar_path(&app, &lang)?)
+        .map_err(|_| format!("No stored checksum to verify '{}' against", lang))?;
+
+    let actual = sha256_hex_of_file(&db_path)?;
+
+    Ok(expected.trim().eq_ignore_ascii_case(&actual))
+}
+
 // Ensure dictionary database exists, decompress if needed
 #[tauri::command]
-async fn ensure_dictionary_db(app: tauri::AppHandle, lang: String) -> Result<DictionaryInfo, String> {
+async fn ensure_dictionary_db(
+    app: tauri::AppHandle,
+    config: tauri::State<'_, AppConfigState>,
+    lang: String,
+) -> Result<DictionaryInfo, String> {
+    validate_lang(&lang)?;
     let mut logs = Vec::new();
-    
+
     let db_filename = format!("dictionary_{}.db", lang);
     let gz_filename = format!("dictionary_{}.db.gz", lang);
 
@@ -41,8 +616,10 @@ async fn ensure_dictionary_db(app: tauri::AppHandle, lang: String) -> Result<Dic
     
     if db_path.exists() {
         logs.push("[RUST] Dictionary DB found.".to_string());
+        let version = std::fs::read_to_string(version_sidecar_path(&app, &lang)?)
+            .unwrap_or_else(|_| "unknown".to_string());
         return Ok(DictionaryInfo {
-            version: "20241115".to_string(),
+            version,
             path: db_path.to_string_lossy().to_string(),
             exists: true,
             logs,
@@ -59,44 +636,366 @@ async fn ensure_dictionary_db(app: tauri::AppHandle, lang: String) -> Result<Dic
 
     if gz_path.exists() {
         logs.push("[RUST] GZ file found, decompressing...".to_string());
-        
-        let bytes = std::fs::read(&gz_path)
-            .map_err(|e| format!("Failed to read GZ file: {}", e))?;
-
-        let mut decoder = GzDecoder::new(&bytes[..]);
-        let mut output_file = File::create(&db_path)
-            .map_err(|e| format!("Failed to create DB file: {}", e))?;
-        
-        let bytes_written = std::io::copy(&mut decoder, &mut output_file)
-            .map_err(|e| format!("Failed to decompress DB: {}", e))?;
-        
+
+        let expected_sha256 = std::fs::read_to_string(expected_sha256_sidecar_path(&app, &lang)?)
+            .ok()
+            .map(|s| s.trim().to_string());
+        let (bytes_written, _) =
+            decompress_gz_to_db(&gz_path, &db_path, expected_sha256.as_deref()).map_err(|e| {
+                std::fs::remove_file(&gz_path).ok();
+                fold_logs_into_error(&logs, &e)
+            })?;
+
         logs.push(format!("[RUST] Successfully decompressed {} bytes", bytes_written));
-        
-        // Optional: remove gz file after successful decompression
-        // std::fs::remove_file(gz_path).ok();
 
+        ensure_dictionary_fts_index(&db_path)?;
+        logs.push("[RUST] FTS index ready.".to_string());
+
+        // Record the checksum of the file as installed, FTS index included.
+        std::fs::write(sha256_sidecar_path(&app, &lang)?, sha256_hex_of_file(&db_path)?)
+            .map_err(|e| format!("Failed to write checksum sidecar: {}", e))?;
+
+        if config.0.lock().await.delete_gz_after_decompress {
+            std::fs::remove_file(&gz_path).ok();
+            logs.push("[RUST] Removed .gz after decompression.".to_string());
+        }
+
+        let version = std::fs::read_to_string(version_sidecar_path(&app, &lang)?)
+            .unwrap_or_else(|_| "unknown".to_string());
         return Ok(DictionaryInfo {
-            version: "20241115".to_string(),
+            version,
             path: db_path.to_string_lossy().to_string(),
             exists: true,
             logs,
         });
     }
 
-    // If we are here, neither DB nor GZ exists.
-    // We return exists: false so the client knows to download it.
-    logs.push("[RUST] Dictionary not found locally.".to_string());
-    
-    Ok(DictionaryInfo {
-        version: "".to_string(),
-        path: "".to_string(),
-        exists: false,
-        logs,
+    // Neither DB nor GZ exists locally; try fetching it from a configured mirror
+    // before giving up and telling the client to download it itself.
+    logs.push("[RUST] Dictionary not found locally, trying configured mirrors...".to_string());
+
+    let catalog = app.state::<DictionaryCatalog>();
+    match download_dictionary(app.clone(), catalog, config, lang.clone()).await {
+        Ok(mut info) => {
+            logs.append(&mut info.logs);
+            info.logs = logs;
+            Ok(info)
+        }
+        Err(e) => {
+            logs.push(format!("[RUST] {}", e));
+            Ok(DictionaryInfo {
+                version: "".to_string(),
+                path: "".to_string(),
+                exists: false,
+                logs,
+            })
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct DictionarySearchHit {
+    entry: DictionaryEntry,
+    snippet: String,
+}
+
+// Full-text search over an installed dictionary's `entries` table, ranked by BM25.
+#[tauri::command]
+async fn search_dictionary(
+    app: tauri::AppHandle,
+    lang: String,
+    query: String,
+    limit: i64,
+) -> Result<Vec<DictionarySearchHit>, String> {
+    validate_lang(&lang)?;
+    let db_path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(format!("dictionary_{}.db", lang));
+
+    if !db_path.exists() {
+        return Err(format!("Dictionary '{}' is not installed", lang));
+    }
+
+    let match_query = build_fts5_match_query(&query);
+    if match_query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ensure_dictionary_fts_index(&db_path)?;
+
+        let conn = rusqlite::Connection::open(&db_path)
+            .map_err(|e| format!("Failed to open dictionary db: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT e.word, e.pronunciation, e.gender, e.meanings, e.notes, e.synonyms, e.see_also,
+                        snippet(dictionary_fts, -1, '<b>', '</b>', '…', 10) AS snippet
+                 FROM dictionary_fts
+                 JOIN entries e ON e.id = dictionary_fts.rowid
+                 WHERE dictionary_fts MATCH ?1
+                 ORDER BY bm25(dictionary_fts)
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to prepare search query: {}", e))?;
+
+        let hits = stmt
+            .query_map(rusqlite::params![match_query, limit], |row| {
+                let meanings: String = row.get(3)?;
+                let notes: String = row.get(4)?;
+                let synonyms: String = row.get(5)?;
+                let see_also: String = row.get(6)?;
+                Ok(DictionarySearchHit {
+                    entry: DictionaryEntry {
+                        word: row.get(0)?,
+                        pronunciation: row.get(1)?,
+                        gender: row.get(2)?,
+                        meanings: json_string_list(&meanings),
+                        notes: json_string_list(&notes),
+                        synonyms: json_string_list(&synonyms),
+                        see_also: json_string_list(&see_also),
+                    },
+                    snippet: row.get(7)?,
+                })
+            })
+            .map_err(|e| format!("Failed to run search query: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read search results: {}", e))?;
+
+        Ok(hits)
     })
+    .await
+    .map_err(|e| format!("Search task panicked: {}", e))?
+}
+
+#[derive(serde::Serialize)]
+struct WordSearchHit {
+    id: i64,
+    original: String,
+    translation: String,
+    article: String,
+    snippet: String,
 }
 
-// Search dictionary - will be called from frontend via SQL plugin directly
-// This is just a helper to show structure
+// Full-text search over the user's saved words, ranked by BM25.
+#[tauri::command]
+async fn search_words(
+    app: tauri::AppHandle,
+    query: String,
+    limit: i64,
+) -> Result<Vec<WordSearchHit>, String> {
+    let instances = app.state::<DbInstances>();
+    let pool = {
+        let instances = instances.0.read().await;
+        instances
+            .get("sqlite:words.db")
+            .cloned()
+            .ok_or_else(|| "words.db connection not found".to_string())?
+    };
+    let DbPool::Sqlite(pool) = pool else {
+        return Err("words.db is not a sqlite connection".to_string());
+    };
+
+    let match_query = build_fts5_match_query(&query);
+    if match_query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(i64, String, String, String, String)> = sqlx::query_as(
+        "SELECT w.id, w.original, w.translation, w.article,
+                snippet(words_fts, -1, '<b>', '</b>', '…', 10) AS snippet
+         FROM words_fts
+         JOIN words w ON w.id = words_fts.rowid
+         WHERE words_fts MATCH ?
+         ORDER BY bm25(words_fts)
+         LIMIT ?",
+    )
+    .bind(&match_query)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("Failed to search words: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, original, translation, article, snippet)| WordSearchHit {
+            id,
+            original,
+            translation,
+            article,
+            snippet,
+        })
+        .collect())
+}
+
+#[derive(serde::Serialize)]
+struct ReviewResult {
+    repetitions: i64,
+    #[serde(rename = "easeFactor")]
+    ease_factor: f64,
+    #[serde(rename = "intervalDays")]
+    interval_days: i64,
+    #[serde(rename = "lastReviewedAt")]
+    last_reviewed_at: i64,
+    #[serde(rename = "nextReviewAt")]
+    next_review_at: i64,
+}
+
+const MIN_EASE_FACTOR: f64 = 1.3;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+struct Sm2Outcome {
+    repetitions: i64,
+    ease_factor: f64,
+    interval_days: i64,
+}
+
+/// Pure SM-2 scheduling step: given a recall `quality` (clamped to 0..=5) and the word's
+/// prior repetitions/ease factor/interval, returns the updated repetitions, ease factor
+/// (floored at `MIN_EASE_FACTOR`), and the interval in days until the next review.
+fn compute_sm2(
+    quality: i64,
+    prev_repetitions: i64,
+    prev_ease_factor: f64,
+    prev_interval_days: i64,
+) -> Sm2Outcome {
+    let quality = quality.clamp(0, 5);
+    let mut repetitions = prev_repetitions;
+
+    let interval_days = if quality >= 3 {
+        repetitions += 1;
+        match repetitions {
+            1 => 1,
+            2 => 6,
+            _ => (prev_interval_days.max(1) as f64 * prev_ease_factor).round() as i64,
+        }
+    } else {
+        repetitions = 0;
+        1
+    };
+
+    let q = quality as f64;
+    let ease_factor =
+        (prev_ease_factor + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02))).max(MIN_EASE_FACTOR);
+
+    Sm2Outcome {
+        repetitions,
+        ease_factor,
+        interval_days,
+    }
+}
+
+#[cfg(test)]
+mod sm2_tests {
+    use super::*;
+
+    #[test]
+    fn quality_below_3_resets_repetitions_and_interval() {
+        for q in 0..3 {
+            let outcome = compute_sm2(q, 5, 2.5, 20);
+            assert_eq!(outcome.repetitions, 0, "q={}", q);
+            assert_eq!(outcome.interval_days, 1, "q={}", q);
+        }
+    }
+
+    #[test]
+    fn first_correct_repetition_schedules_one_day() {
+        let outcome = compute_sm2(4, 0, 2.5, 0);
+        assert_eq!(outcome.repetitions, 1);
+        assert_eq!(outcome.interval_days, 1);
+    }
+
+    #[test]
+    fn second_correct_repetition_schedules_six_days() {
+        let outcome = compute_sm2(4, 1, 2.5, 1);
+        assert_eq!(outcome.repetitions, 2);
+        assert_eq!(outcome.interval_days, 6);
+    }
+
+    #[test]
+    fn third_plus_repetition_multiplies_interval_by_ease_factor() {
+        let outcome = compute_sm2(4, 2, 2.5, 6);
+        assert_eq!(outcome.repetitions, 3);
+        assert_eq!(outcome.interval_days, (6.0_f64 * 2.5).round() as i64);
+    }
+
+    #[test]
+    fn ease_factor_is_floored_at_minimum() {
+        let outcome = compute_sm2(0, 3, 1.3, 10);
+        assert!((outcome.ease_factor - MIN_EASE_FACTOR).abs() < f64::EPSILON);
+
+        let outcome = compute_sm2(0, 3, 1.35, 10);
+        assert!(outcome.ease_factor >= MIN_EASE_FACTOR);
+    }
+
+    #[test]
+    fn perfect_recall_increases_ease_factor() {
+        let outcome = compute_sm2(5, 2, 2.5, 6);
+        assert!(outcome.ease_factor > 2.5);
+    }
+}
+
+// Grade a word using the SM-2 spaced-repetition algorithm and schedule its next review.
+#[tauri::command]
+async fn grade_word(
+    app: tauri::AppHandle,
+    word_id: i64,
+    quality: i64,
+) -> Result<ReviewResult, String> {
+    let instances = app.state::<DbInstances>();
+    let pool = {
+        let instances = instances.0.read().await;
+        instances
+            .get("sqlite:words.db")
+            .cloned()
+            .ok_or_else(|| "words.db connection not found".to_string())?
+    };
+    let DbPool::Sqlite(pool) = pool else {
+        return Err("words.db is not a sqlite connection".to_string());
+    };
+
+    let row: (i64, f64, i64) = sqlx::query_as(
+        "SELECT repetitions, ease_factor, nextReviewAt - lastReviewedAt FROM words WHERE id = ?",
+    )
+    .bind(word_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| format!("Failed to load word {}: {}", word_id, e))?
+    .ok_or_else(|| format!("No word with id {}", word_id))?;
+
+    let (prev_repetitions, prev_ease_factor, prev_interval_seconds) = row;
+    let prev_interval_days = (prev_interval_seconds / SECONDS_PER_DAY).max(1);
+
+    let outcome = compute_sm2(quality, prev_repetitions, prev_ease_factor, prev_interval_days);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Clock error: {}", e))?
+        .as_secs() as i64;
+    let next_review_at = now + outcome.interval_days * SECONDS_PER_DAY;
+
+    sqlx::query(
+        "UPDATE words SET repetitions = ?, ease_factor = ?, lastReviewedAt = ?, nextReviewAt = ? WHERE id = ?",
+    )
+    .bind(outcome.repetitions)
+    .bind(outcome.ease_factor)
+    .bind(now)
+    .bind(next_review_at)
+    .bind(word_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| format!("Failed to update word {}: {}", word_id, e))?;
+
+    Ok(ReviewResult {
+        repetitions: outcome.repetitions,
+        ease_factor: outcome.ease_factor,
+        interval_days: outcome.interval_days,
+        last_reviewed_at: now,
+        next_review_at,
+    })
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -121,6 +1020,37 @@ pub fn run() {
                   ALTER TABLE words ADD COLUMN lastReviewedAt INTEGER NOT NULL DEFAULT 0;
                   ALTER TABLE words ADD COLUMN nextReviewAt INTEGER NOT NULL DEFAULT 0;",
             kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 3,
+            description: "add_sm2_fields",
+            sql: "ALTER TABLE words ADD COLUMN ease_factor REAL NOT NULL DEFAULT 2.5;
+                  ALTER TABLE words ADD COLUMN repetitions INTEGER NOT NULL DEFAULT 0;",
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 4,
+            description: "add_words_fts",
+            sql: "CREATE VIRTUAL TABLE IF NOT EXISTS words_fts USING fts5(
+                    original, translation, content='words', content_rowid='id'
+                  );
+                  INSERT INTO words_fts(rowid, original, translation)
+                    SELECT id, original, translation FROM words;
+                  CREATE TRIGGER words_ai AFTER INSERT ON words BEGIN
+                    INSERT INTO words_fts(rowid, original, translation)
+                      VALUES (new.id, new.original, new.translation);
+                  END;
+                  CREATE TRIGGER words_ad AFTER DELETE ON words BEGIN
+                    INSERT INTO words_fts(words_fts, rowid, original, translation)
+                      VALUES('delete', old.id, old.original, old.translation);
+                  END;
+                  CREATE TRIGGER words_au AFTER UPDATE ON words BEGIN
+                    INSERT INTO words_fts(words_fts, rowid, original, translation)
+                      VALUES('delete', old.id, old.original, old.translation);
+                    INSERT INTO words_fts(rowid, original, translation)
+                      VALUES (new.id, new.original, new.translation);
+                  END;",
+            kind: MigrationKind::Up,
         }
     ];
 
@@ -134,7 +1064,24 @@ pub fn run() {
                 .add_migrations("sqlite:words.db", migrations)
                 .build(),
         )
-        .invoke_handler(tauri::generate_handler![ensure_dictionary_db])
+        .manage(DictionaryCatalog::default())
+        .setup(|app| {
+            let config = load_app_config(app.handle());
+            app.manage(AppConfigState(tokio::sync::Mutex::new(config)));
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            ensure_dictionary_db,
+            grade_word,
+            get_config,
+            set_config,
+            fetch_dictionary_catalog,
+            list_installed_dictionaries,
+            download_dictionary,
+            verify_dictionary_db,
+            search_dictionary,
+            search_words
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }